@@ -0,0 +1,60 @@
+use crate::*;
+use near_sdk::serde_json;
+
+//the JSON payload a seller passes as `msg` to `nft_approve` when listing a token
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SaleArgs {
+    pub sale_conditions: SalePriceInYoctoNear,
+    #[serde(default)]
+    pub auction: Option<AuctionConditions>,
+}
+
+trait NonFungibleTokenApprovalReceiver {
+    fn nft_on_approve(&mut self, token_id: TokenId, owner_id: AccountId, approval_id: u64, msg: String);
+}
+
+#[near_bindgen]
+impl NonFungibleTokenApprovalReceiver for Contract {
+    //called by an NFT contract's `nft_approve` once the market has been approved to sell a token.
+    //this is where a `Sale` actually enters `self.sales`/`self.by_owner_id`.
+    fn nft_on_approve(&mut self, token_id: TokenId, owner_id: AccountId, approval_id: u64, msg: String) {
+        self.assert_not_paused();
+
+        let nft_contract_id = env::predecessor_account_id();
+        let SaleArgs { sale_conditions, auction } =
+            serde_json::from_str(&msg).expect("Not valid SaleArgs");
+
+        if let Some(auction) = &auction {
+            Self::internal_validate_auction(auction);
+        }
+
+        self.assert_storage_covers_new_sale(&owner_id);
+
+        let contract_and_token_id = format!("{}{}{}", nft_contract_id, DELIMETER, token_id);
+
+        self.sales.insert(
+            &contract_and_token_id,
+            &Sale {
+                owner_id: owner_id.clone(),
+                approval_id,
+                nft_contract_id: nft_contract_id.to_string(),
+                token_id: token_id.clone(),
+                sale_conditions,
+                auction,
+            },
+        );
+
+        let mut by_owner_id = self.by_owner_id.get(&owner_id).unwrap_or_else(|| {
+            UnorderedSet::new(
+                StorageKey::ByOwnerIdInner {
+                    account_id_hash: hash_account_id(&owner_id),
+                }
+                .try_to_vec()
+                .unwrap(),
+            )
+        });
+        by_owner_id.insert(&contract_and_token_id);
+        self.by_owner_id.insert(&owner_id, &by_owner_id);
+    }
+}
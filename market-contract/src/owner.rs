@@ -0,0 +1,104 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    //grant the Admin role to `account_id`. Only the contract owner can do this.
+    pub fn grant_admin(&mut self, account_id: AccountId) {
+        self.assert_owner();
+
+        if self.admins.insert(&account_id) {
+            env::log_str(&format!("Granted Admin role to {}", account_id));
+        }
+    }
+
+    //revoke the Admin role from `account_id`. Only the contract owner can do this.
+    pub fn revoke_admin(&mut self, account_id: AccountId) {
+        self.assert_owner();
+
+        if self.admins.remove(&account_id) {
+            env::log_str(&format!("Revoked Admin role from {}", account_id));
+        }
+    }
+
+    pub fn is_admin(&self, account_id: AccountId) -> bool {
+        self.admins.contains(&account_id)
+    }
+
+    //halt `offer`, `update_price` and `process_purchase`. Only an Admin can do this.
+    pub fn pause(&mut self) {
+        self.assert_admin();
+        self.paused = true;
+    }
+
+    //resume trading. Only an Admin can do this.
+    pub fn unpause(&mut self) {
+        self.assert_admin();
+        self.paused = false;
+    }
+
+    pub(crate) fn assert_owner(&self) {
+        assert_eq!(
+            env::predecessor_account_id(),
+            self.owner_id,
+            "Only the contract owner can call this method"
+        );
+    }
+
+    //the owner is always treated as an Admin, so a freshly deployed marketplace (with an empty
+    //`admins` set) can still have its first Admin granted and be paused/unpaused.
+    pub(crate) fn assert_admin(&self) {
+        let predecessor = env::predecessor_account_id();
+        assert!(
+            predecessor == self.owner_id || self.admins.contains(&predecessor),
+            "Only an Admin can call this method"
+        );
+    }
+
+    pub(crate) fn assert_not_paused(&self) {
+        assert!(!self.paused, "Marketplace is paused");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::VMContextBuilder;
+    use near_sdk::testing_env;
+
+    fn account(name: &str) -> AccountId {
+        name.parse().unwrap()
+    }
+
+    fn context(predecessor: AccountId) {
+        let mut builder = VMContextBuilder::new();
+        builder.predecessor_account_id(predecessor);
+        testing_env!(builder.build());
+    }
+
+    //the bootstrap scenario the fallback exists for: a freshly deployed contract's `admins` set
+    //is empty, so only the owner fallback lets anyone ever grant the first real Admin.
+    #[test]
+    fn owner_can_pause_and_grant_the_first_admin_before_any_admin_is_granted() {
+        let owner = account("owner.near");
+        context(owner.clone());
+        let mut contract = Contract::new(owner.clone());
+
+        contract.pause();
+        assert!(contract.paused);
+
+        let first_admin = account("admin.near");
+        contract.grant_admin(first_admin.clone());
+        assert!(contract.is_admin(first_admin));
+    }
+
+    #[test]
+    #[should_panic(expected = "Only an Admin can call this method")]
+    fn non_owner_non_admin_cannot_pause() {
+        let owner = account("owner.near");
+        context(owner.clone());
+        let mut contract = Contract::new(owner);
+
+        context(account("rando.near"));
+        contract.pause();
+    }
+}
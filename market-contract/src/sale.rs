@@ -10,12 +10,56 @@ pub struct Sale {
     pub nft_contract_id: String,
     pub token_id: String,
     pub sale_conditions: SalePriceInYoctoNear,
+    pub auction: Option<AuctionConditions>,
+}
+
+//a declining-price (Dutch auction) schedule attached to a sale
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AuctionConditions {
+    pub start_price: U128,
+    pub end_price: U128,
+    pub start_timestamp: u64,
+    pub duration: u64,
+}
+
+//linear price decay from start_price at start_timestamp down to end_price once duration elapses
+pub(crate) fn internal_auction_price(auction: &AuctionConditions, now: u64) -> U128 {
+    let elapsed = std::cmp::min(now.saturating_sub(auction.start_timestamp), auction.duration);
+
+    let start_price = auction.start_price.0;
+    let end_price = auction.end_price.0;
+
+    let price =
+        start_price - (start_price - end_price) * u128::from(elapsed) / u128::from(auction.duration);
+
+    U128(price)
 }
 
 #[near_bindgen]
 impl Contract {
-    
-    //removes a sale from the market. 
+
+    //validate an auction descriptor before it's attached to a sale (called at listing time)
+    pub(crate) fn internal_validate_auction(auction: &AuctionConditions) {
+        assert!(auction.duration > 0, "Auction duration must be greater than 0");
+        assert!(
+            auction.start_price.0 >= auction.end_price.0,
+            "Auction start_price must be greater than or equal to end_price"
+        );
+    }
+
+    //compute the current linearly-declining price for an auction sale. Falls back to the
+    //plain sale_conditions price if the sale isn't an auction.
+    pub fn current_price(&self, contract_and_token_id: String) -> U128 {
+        let sale = self.sales.get(&contract_and_token_id).expect("No sale");
+
+        match &sale.auction {
+            Some(auction) => internal_auction_price(auction, env::block_timestamp()),
+            None => sale.sale_conditions,
+        }
+    }
+
+    //removes a sale from the market.
     #[payable]
     pub fn remove_sale(&mut self, nft_contract_id: AccountId, token_id: String) {
         assert_one_yocto();
@@ -32,6 +76,7 @@ impl Contract {
         token_id: String,
         price: U128,
     ) {
+        self.assert_not_paused();
         assert_one_yocto();
         
         let contract_id: AccountId = nft_contract_id.into();
@@ -49,9 +94,42 @@ impl Contract {
         self.sales.insert(&contract_and_token_id, &sale);
     }
 
+    //attach (or clear) a Dutch-auction schedule on an existing sale. Validated the same way a
+    //fresh listing would be, so a sale can never end up with a bricked auction descriptor.
+    #[payable]
+    pub fn update_auction(
+        &mut self,
+        nft_contract_id: AccountId,
+        token_id: String,
+        auction: Option<AuctionConditions>,
+    ) {
+        self.assert_not_paused();
+        assert_one_yocto();
+
+        if let Some(auction) = &auction {
+            Self::internal_validate_auction(auction);
+        }
+
+        let contract_id: AccountId = nft_contract_id.into();
+        let contract_and_token_id = format!("{}{}{}", contract_id, DELIMETER, token_id);
+
+        let mut sale = self.sales.get(&contract_and_token_id).expect("No sale");
+
+        assert_eq!(
+            env::predecessor_account_id(),
+            sale.owner_id,
+            "Must be sale owner"
+        );
+
+        sale.auction = auction;
+        self.sales.insert(&contract_and_token_id, &sale);
+    }
+
     //place an offer on a specific sale. The sale will go through as long as your deposit is greater than or equal to the list price
     #[payable]
     pub fn offer(&mut self, nft_contract_id: AccountId, token_id: String) {
+        self.assert_not_paused();
+
         let deposit = env::attached_deposit();
         assert!(deposit > 0, "Attached deposit must be greater than 0");
 
@@ -60,18 +138,34 @@ impl Contract {
         let contract_and_token_id = format!("{}{}{}", contract_id, DELIMETER, token_id);
         
         let sale = self.sales.get(&contract_and_token_id).expect("No sale");
-        
+
         let buyer_id = env::predecessor_account_id();
         assert_ne!(sale.owner_id, buyer_id, "Cannot bid on your own sale.");
-        
-        let price = sale.sale_conditions.0;
 
-        assert!(deposit >= price, "Attached deposit must be greater than or equal to the current price: {:?}", price);
- 
+        //an auction settles at the computed declining price, refunding whatever the buyer
+        //overpaid; a fixed-price sale keeps the full deposit as before
+        let price = if sale.auction.is_some() {
+            let current_price = self.current_price(contract_and_token_id).0;
+
+            assert!(deposit >= current_price, "Attached deposit must be greater than or equal to the current price: {:?}", current_price);
+
+            let excess = deposit - current_price;
+            if excess > 0 {
+                Promise::new(buyer_id.clone()).transfer(excess);
+            }
+
+            current_price
+        } else {
+            let list_price = sale.sale_conditions.0;
+            assert!(deposit >= list_price, "Attached deposit must be greater than or equal to the current price: {:?}", list_price);
+
+            deposit
+        };
+
         self.process_purchase(
             contract_id,
             token_id,
-            U128(deposit),
+            U128(price),
             buyer_id,
         );
     }
@@ -86,6 +180,8 @@ impl Contract {
         price: U128,
         buyer_id: AccountId,
     ) -> Promise {
+        self.assert_not_paused();
+
         let sale = self.internal_remove_sale(nft_contract_id.clone(), token_id.clone());
 
         //initiate a cross contract call to the nft contract. This will transfer the token to the buyer and return
@@ -165,4 +261,51 @@ trait ExtSelf {
         buyer_id: AccountId,
         price: U128,
     ) -> Promise;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn auction() -> AuctionConditions {
+        AuctionConditions {
+            start_price: U128(1_000),
+            end_price: U128(100),
+            start_timestamp: 1_000,
+            duration: 1_000,
+        }
+    }
+
+    #[test]
+    fn price_at_start_is_start_price() {
+        let auction = auction();
+        assert_eq!(internal_auction_price(&auction, auction.start_timestamp).0, 1_000);
+    }
+
+    #[test]
+    fn price_at_midpoint_is_halfway_between() {
+        let auction = auction();
+        let midpoint = auction.start_timestamp + auction.duration / 2;
+        assert_eq!(internal_auction_price(&auction, midpoint).0, 550);
+    }
+
+    #[test]
+    fn price_at_end_is_end_price() {
+        let auction = auction();
+        let end = auction.start_timestamp + auction.duration;
+        assert_eq!(internal_auction_price(&auction, end).0, 100);
+    }
+
+    #[test]
+    fn price_after_end_clamps_to_end_price() {
+        let auction = auction();
+        let after_end = auction.start_timestamp + auction.duration + 10_000;
+        assert_eq!(internal_auction_price(&auction, after_end).0, 100);
+    }
+
+    #[test]
+    fn price_before_start_clamps_to_start_price() {
+        let auction = auction();
+        assert_eq!(internal_auction_price(&auction, 0).0, 1_000);
+    }
 }
\ No newline at end of file
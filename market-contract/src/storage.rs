@@ -0,0 +1,146 @@
+use crate::*;
+
+//fixed byte cost charged against a seller's storage balance for each active sale they hold
+pub const STORAGE_PER_SALE: u64 = 1000;
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StorageBalance {
+    pub total: U128,
+    pub available: U128,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StorageBalanceBounds {
+    pub min: U128,
+    pub max: Option<U128>,
+}
+
+//each open sale locks a fixed STORAGE_PER_SALE bytes' worth of the seller's deposit
+fn storage_locked(num_sales: u64, byte_cost: Balance) -> Balance {
+    Balance::from(num_sales * STORAGE_PER_SALE) * byte_cost
+}
+
+fn storage_available(total: Balance, num_sales: u64, byte_cost: Balance) -> Balance {
+    total.saturating_sub(storage_locked(num_sales, byte_cost))
+}
+
+fn storage_covers_new_sale(total: Balance, num_sales: u64, byte_cost: Balance) -> bool {
+    total >= storage_locked(num_sales + 1, byte_cost)
+}
+
+#[near_bindgen]
+impl Contract {
+    //how much of `account_id`'s storage deposit (in yoctoNEAR) their currently-open sales are locking
+    pub(crate) fn storage_locked_for(&self, account_id: &AccountId) -> Balance {
+        let num_sales = self
+            .by_owner_id
+            .get(account_id)
+            .map(|sales| sales.len())
+            .unwrap_or(0);
+
+        storage_locked(num_sales, env::storage_byte_cost())
+    }
+
+    //deposit NEAR to cover the storage cost of future listings
+    #[payable]
+    pub fn storage_deposit(&mut self, account_id: Option<AccountId>) -> StorageBalance {
+        let deposit = env::attached_deposit();
+        assert!(deposit > 0, "Requires attached deposit to be greater than 0");
+
+        let account_id = account_id.unwrap_or_else(env::predecessor_account_id);
+
+        let balance = self.storage_deposits.get(&account_id).unwrap_or(0) + deposit;
+        self.storage_deposits.insert(&account_id, &balance);
+
+        self.storage_balance_of(account_id)
+    }
+
+    //withdraw any deposited balance that isn't locked by the account's current listings
+    #[payable]
+    pub fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance {
+        assert_one_yocto();
+
+        let account_id = env::predecessor_account_id();
+        let total = self.storage_deposits.get(&account_id).unwrap_or(0);
+        let locked = self.storage_locked_for(&account_id);
+        let available = total.saturating_sub(locked);
+
+        let to_withdraw = amount.map(|a| a.0).unwrap_or(available);
+        assert!(to_withdraw <= available, "Cannot withdraw more than available balance");
+
+        self.storage_deposits
+            .insert(&account_id, &(total - to_withdraw));
+
+        if to_withdraw > 0 {
+            Promise::new(account_id.clone()).transfer(to_withdraw);
+        }
+
+        self.storage_balance_of(account_id)
+    }
+
+    pub fn storage_balance_of(&self, account_id: AccountId) -> StorageBalance {
+        let total = self.storage_deposits.get(&account_id).unwrap_or(0);
+        let num_sales = self
+            .by_owner_id
+            .get(&account_id)
+            .map(|sales| sales.len())
+            .unwrap_or(0);
+
+        StorageBalance {
+            total: U128(total),
+            available: U128(storage_available(total, num_sales, env::storage_byte_cost())),
+        }
+    }
+
+    pub fn storage_balance_bounds(&self) -> StorageBalanceBounds {
+        StorageBalanceBounds {
+            min: U128(Balance::from(STORAGE_PER_SALE) * env::storage_byte_cost()),
+            max: None,
+        }
+    }
+
+    //assert that `account_id` has deposited enough storage balance to cover one more open sale
+    pub(crate) fn assert_storage_covers_new_sale(&self, account_id: &AccountId) {
+        let total = self.storage_deposits.get(account_id).unwrap_or(0);
+        let num_sales = self
+            .by_owner_id
+            .get(account_id)
+            .map(|sales| sales.len())
+            .unwrap_or(0);
+
+        assert!(
+            storage_covers_new_sale(total, num_sales, env::storage_byte_cost()),
+            "Insufficient storage deposit to cover this listing; call storage_deposit first"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn available_subtracts_locked_sales() {
+        let byte_cost = 100;
+        let total = storage_locked(3, byte_cost) + 50;
+        assert_eq!(storage_available(total, 3, byte_cost), 50);
+    }
+
+    #[test]
+    fn available_saturates_at_zero_when_total_is_under_locked() {
+        let byte_cost = 100;
+        let total = storage_locked(3, byte_cost) - 10;
+        assert_eq!(storage_available(total, 3, byte_cost), 0);
+    }
+
+    #[test]
+    fn covers_new_sale_requires_room_for_one_more_listing() {
+        let byte_cost = 100;
+        let exact = storage_locked(2, byte_cost);
+
+        assert!(storage_covers_new_sale(exact, 1, byte_cost));
+        assert!(!storage_covers_new_sale(exact - 1, 1, byte_cost));
+    }
+}
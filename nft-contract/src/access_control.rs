@@ -0,0 +1,112 @@
+use crate::*;
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum Role {
+    Admin,
+    Minter,
+}
+
+#[near_bindgen]
+impl Contract {
+    //grant `role` to `account_id`. Only an existing Admin can do this.
+    pub fn acl_grant_role(&mut self, account_id: AccountId, role: Role) {
+        self.assert_role(Role::Admin);
+
+        let mut roles = self.roles.get(&account_id).unwrap_or_default();
+        roles.insert(role);
+        self.roles.insert(&account_id, &roles);
+    }
+
+    //revoke `role` from `account_id`. Only an existing Admin can do this.
+    pub fn acl_revoke_role(&mut self, account_id: AccountId, role: Role) {
+        self.assert_role(Role::Admin);
+
+        if let Some(mut roles) = self.roles.get(&account_id) {
+            roles.remove(&role);
+            if roles.is_empty() {
+                self.roles.remove(&account_id);
+            } else {
+                self.roles.insert(&account_id, &roles);
+            }
+        }
+    }
+
+    pub fn acl_has_role(&self, account_id: AccountId, role: Role) -> bool {
+        self.roles
+            .get(&account_id)
+            .map(|roles| roles.contains(&role))
+            .unwrap_or(false)
+    }
+
+    //halt all token movement. Only an Admin can do this.
+    pub fn pause(&mut self) {
+        self.assert_role(Role::Admin);
+        self.paused = true;
+    }
+
+    //resume token movement. Only an Admin can do this.
+    pub fn unpause(&mut self) {
+        self.assert_role(Role::Admin);
+        self.paused = false;
+    }
+
+    //the owner always satisfies any role check, so a freshly deployed contract (with an empty
+    //`roles` map) can still have its first Admin granted rather than being permanently locked out.
+    pub(crate) fn assert_role(&self, role: Role) {
+        let predecessor = env::predecessor_account_id();
+        assert!(
+            predecessor == self.owner_id || self.acl_has_role(predecessor, role),
+            "Predecessor does not have the {:?} role",
+            role
+        );
+    }
+
+    pub(crate) fn assert_not_paused(&self) {
+        assert!(!self.paused, "Contract is paused");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::VMContextBuilder;
+    use near_sdk::testing_env;
+
+    fn account(name: &str) -> AccountId {
+        name.parse().unwrap()
+    }
+
+    fn context(predecessor: AccountId) {
+        let mut builder = VMContextBuilder::new();
+        builder.predecessor_account_id(predecessor);
+        testing_env!(builder.build());
+    }
+
+    //the bootstrap scenario the fallback exists for: a freshly deployed contract's `roles` map
+    //is empty, so only the owner fallback lets anyone ever grant the first Admin/Minter.
+    #[test]
+    fn owner_can_pause_and_grant_the_first_role_before_any_role_is_granted() {
+        let owner = account("owner.near");
+        context(owner.clone());
+        let mut contract = Contract::new_default_meta(owner.clone());
+
+        contract.pause();
+        assert!(contract.paused);
+
+        let minter = account("minter.near");
+        contract.acl_grant_role(minter.clone(), Role::Minter);
+        assert!(contract.acl_has_role(minter, Role::Minter));
+    }
+
+    #[test]
+    #[should_panic(expected = "does not have the Admin role")]
+    fn non_owner_without_a_role_cannot_pause() {
+        let owner = account("owner.near");
+        context(owner.clone());
+        let mut contract = Contract::new_default_meta(owner);
+
+        context(account("rando.near"));
+        contract.pause();
+    }
+}
@@ -18,6 +18,12 @@ pub trait NonFungibleTokenCore {
 
     //revoke all accounts from transferring the token on your behalf
     fn nft_revoke_all(&mut self, token_id: TokenId);
+
+    //approve an account ID as an operator over every token the caller owns, optionally expiring
+    fn nft_approve_all(&mut self, account_id: AccountId, expires_at: Option<u64>);
+
+    //revoke every operator approval the caller has granted over their collection
+    fn nft_revoke_all_operators(&mut self);
 }
 
 #[ext_contract(ext_non_fungible_approval_receiver)]
@@ -66,7 +72,15 @@ impl NonFungibleTokenCore for Contract {
         token.next_approval_id += 1;
         self.tokens_by_id.insert(&token_id, &token);
 
-        refund_deposit(storage_used);
+        //any attached deposit tops up the predecessor's prepaid storage balance first, so an
+        //approver who still front-loads NEAR per call (rather than pre-funding separately)
+        //isn't silently out of pocket
+        let predecessor_account_id = env::predecessor_account_id();
+        let attached = env::attached_deposit();
+        if attached > 0 {
+            self.internal_storage_deposit(&predecessor_account_id, attached);
+        }
+        self.internal_storage_debit(&predecessor_account_id, storage_used);
 
         if let Some(msg) = msg {
             ext_non_fungible_approval_receiver::ext(account_id)
@@ -89,6 +103,11 @@ impl NonFungibleTokenCore for Contract {
         //get the token object from the token_id
         let token = self.tokens_by_id.get(&token_id).expect("No token");
 
+        //an unexpired operator approval covers every token the owner holds
+        if self.internal_is_operator_approved(&token.owner_id, &approved_account_id) {
+            return true;
+        }
+
 		let approval = token.approved_account_ids.get(&approved_account_id);
 
         //if there was some approval ID found for the account ID
@@ -122,7 +141,7 @@ impl NonFungibleTokenCore for Contract {
             .remove(&account_id)
             .is_some()
         {
-            refund_approved_account_ids_iter(predecessor_account_id, [account_id].iter());
+            self.internal_storage_credit(&predecessor_account_id, bytes_for_approved_account_id(&account_id));
 
             self.tokens_by_id.insert(&token_id, &token);
         }
@@ -139,9 +158,52 @@ impl NonFungibleTokenCore for Contract {
         assert_eq!(&predecessor_account_id, &token.owner_id);
 
         if !token.approved_account_ids.is_empty() {
-            refund_approved_account_ids(predecessor_account_id, &token.approved_account_ids);
+            let storage_released: u64 = token
+                .approved_account_ids
+                .keys()
+                .map(bytes_for_approved_account_id)
+                .sum();
+            self.internal_storage_credit(&predecessor_account_id, storage_released);
             token.approved_account_ids.clear();
             self.tokens_by_id.insert(&token_id, &token);
         }
     }
+
+    //approve an account ID as an operator over every token the predecessor owns
+    #[payable]
+    fn nft_approve_all(&mut self, account_id: AccountId, expires_at: Option<u64>) {
+        assert_at_least_one_yocto();
+
+        let owner_id = env::predecessor_account_id();
+
+        let mut operators = self.operator_approvals.get(&owner_id).unwrap_or_default();
+        let is_new_approval = operators.insert(account_id.clone(), expires_at).is_none();
+        self.operator_approvals.insert(&owner_id, &operators);
+
+        let storage_used = if is_new_approval {
+            bytes_for_approved_account_id(&account_id)
+        } else {
+            0
+        };
+
+        //top up the owner's prepaid storage balance with whatever they attached before debiting
+        let attached = env::attached_deposit();
+        if attached > 0 {
+            self.internal_storage_deposit(&owner_id, attached);
+        }
+        self.internal_storage_debit(&owner_id, storage_used);
+    }
+
+    //revoke every operator approval the predecessor has granted over their collection
+    #[payable]
+    fn nft_revoke_all_operators(&mut self) {
+        assert_one_yocto();
+
+        let owner_id = env::predecessor_account_id();
+
+        if let Some(operators) = self.operator_approvals.remove(&owner_id) {
+            let storage_released: u64 = operators.keys().map(bytes_for_approved_account_id).sum();
+            self.internal_storage_credit(&owner_id, storage_released);
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,46 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    //burn one or more tokens owned by the predecessor, freeing all associated storage
+    #[payable]
+    pub fn nft_burn(&mut self, token_ids: Vec<TokenId>, memo: Option<String>) {
+        assert_one_yocto();
+
+        let owner_id = env::predecessor_account_id();
+
+        for token_id in token_ids.iter() {
+            let token = self.tokens_by_id.get(token_id).expect("No token");
+
+            assert_eq!(&owner_id, &token.owner_id, "Predecessor must be the token owner.");
+
+            if !token.approved_account_ids.is_empty() {
+                //approval storage was prepaid through the storage-balance ledger, so it's
+                //credited back there rather than refunded as a direct transfer
+                let storage_released: u64 = token
+                    .approved_account_ids
+                    .keys()
+                    .map(bytes_for_approved_account_id)
+                    .sum();
+                self.internal_storage_credit(&owner_id, storage_released);
+            }
+
+            self.internal_remove_token_from_owner(&owner_id, token_id);
+            self.tokens_by_id.remove(token_id);
+            self.token_metadata_by_id.remove(token_id);
+        }
+
+        let nft_burn_log: EventLog = EventLog {
+            standard: NFT_STANDARD_NAME.to_string(),
+            version: NFT_METADATA_SPEC.to_string(),
+            event: EventLogVariant::NftBurn(vec![NftBurnLog {
+                owner_id: owner_id.to_string(),
+                authorized_id: None,
+                token_ids: token_ids.iter().map(|token_id| token_id.to_string()).collect(),
+                memo,
+            }]),
+        };
+
+        env::log_str(&nft_burn_log.to_string());
+    }
+}
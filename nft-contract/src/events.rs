@@ -10,6 +10,7 @@ use near_sdk::serde::{Deserialize, Serialize};
 pub enum EventLogVariant {
     NftMint(Vec<NftMintLog>),
     NftTransfer(Vec<NftTransferLog>),
+    NftBurn(Vec<NftBurnLog>),
 }
 
 /// Interface to capture data about an event
@@ -74,6 +75,27 @@ pub struct NftTransferLog {
     pub memo: Option<String>,
 }
 
+/// An event log to capture token burning
+///
+/// Arguments
+/// * `owner_id`: "account.near"
+/// * `authorized_id`: approved account that burned the tokens, if any
+/// * `token_ids`: ["1", "abc"]
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftBurnLog {
+    pub owner_id: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorized_id: Option<String>,
+
+    pub token_ids: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -131,4 +153,20 @@ mod tests {
         };
         assert_eq!(expected, log.to_string());
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn nep_format_burn() {
+        let expected = r#"EVENT_JSON:{"standard":"nep177","version":"2.0.0","event":"nft_burn","data":[{"owner_id":"bdrv7.testnet","authorized_id":"market.bdrv7.testnet","token_ids":["test_token","abc"],"memo":"test_memo"}]}"#;
+        let log = EventLog {
+            standard: "nep177".to_string(),
+            version: "2.0.0".to_string(),
+            event: EventLogVariant::NftBurn(vec![NftBurnLog {
+                owner_id: "bdrv7.testnet".to_owned(),
+                authorized_id: Some("market.bdrv7.testnet".to_string()),
+                token_ids: vec!["test_token".to_string(), "abc".to_string()],
+                memo: Some("test_memo".to_owned()),
+            }]),
+        };
+        assert_eq!(expected, log.to_string());
+    }
+}
\ No newline at end of file
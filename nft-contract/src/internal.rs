@@ -12,28 +12,6 @@ pub(crate) fn bytes_for_approved_account_id(account_id: &AccountId) -> u64 {
     account_id.as_str().len() as u64 + 4 + size_of::<u64>() as u64
 }
 
-//refund the storage taken up by passed in approved account IDs and send the funds to the passed in account ID. 
-pub(crate) fn refund_approved_account_ids_iter<'a, I>(
-    account_id: AccountId,
-    approved_account_ids: I, 
-) -> Promise
-where
-    I: Iterator<Item = &'a AccountId>,
-{
-    //get the storage total by going through and summing all the bytes for each approved account IDs
-    let storage_released: u64 = approved_account_ids.map(bytes_for_approved_account_id).sum();
-    //transfer the account the storage that is released
-    Promise::new(account_id).transfer(Balance::from(storage_released) * env::storage_byte_cost())
-}
-
-//refund a map of approved account IDs and send the funds to the passed in account ID
-pub(crate) fn refund_approved_account_ids(
-    account_id: AccountId,
-    approved_account_ids: &HashMap<AccountId, u64>,
-) -> Promise {
-    refund_approved_account_ids_iter(account_id, approved_account_ids.keys())
-}
-
 //used to generate a unique prefix in our storage collections (this is to avoid data collisions)
 pub(crate) fn hash_account_id(account_id: &AccountId) -> CryptoHash {
     let mut hash = CryptoHash::default();
@@ -50,6 +28,15 @@ pub(crate) fn assert_one_yocto() {
     )
 }
 
+//whether an operator approval with the given expiration is still active at `now`. `None` means
+//no expiration was set.
+pub(crate) fn operator_is_active(expires_at: Option<u64>, now: u64) -> bool {
+    match expires_at {
+        Some(expires_at) => expires_at > now,
+        None => true,
+    }
+}
+
 //Assert that the user has attached at least 1 yoctoNEAR (for security reasons and to pay for storage)
 pub(crate) fn assert_at_least_one_yocto() {
     assert!(
@@ -77,6 +64,51 @@ pub(crate) fn refund_deposit(storage_used: u64) {
 }
 
 impl Contract {
+    //read-only check for whether `operator_id` holds an unexpired collection-wide operator
+    //approval over `owner_id`'s tokens. Used from view methods, so expired entries are simply
+    //ignored rather than pruned; see `internal_prune_and_check_operator` for the mutating path.
+    pub(crate) fn internal_is_operator_approved(
+        &self,
+        owner_id: &AccountId,
+        operator_id: &AccountId,
+    ) -> bool {
+        self.operator_approvals
+            .get(owner_id)
+            .and_then(|operators| operators.get(operator_id).copied())
+            .map(|expires_at| operator_is_active(expires_at, env::block_timestamp()))
+            .unwrap_or(false)
+    }
+
+    //same check as `internal_is_operator_approved`, but lazily prunes the entry if it has expired
+    pub(crate) fn internal_prune_and_check_operator(
+        &mut self,
+        owner_id: &AccountId,
+        operator_id: &AccountId,
+    ) -> bool {
+        let mut operators = match self.operator_approvals.get(owner_id) {
+            Some(operators) => operators,
+            None => return false,
+        };
+
+        let expires_at = match operators.get(operator_id) {
+            Some(expires_at) => *expires_at,
+            None => return false,
+        };
+
+        match expires_at {
+            Some(expires_at) if expires_at <= env::block_timestamp() => {
+                operators.remove(operator_id);
+                if operators.is_empty() {
+                    self.operator_approvals.remove(owner_id);
+                } else {
+                    self.operator_approvals.insert(owner_id, &operators);
+                }
+                false
+            }
+            _ => true,
+        }
+    }
+
     //add a token to the set of tokens an owner has
     pub(crate) fn internal_add_token_to_owner(
         &mut self,
@@ -122,6 +154,8 @@ impl Contract {
     }
 
     //transfers the NFT to the receiver_id (internal method and can't be called directly via CLI).
+    //always emits its own NftTransfer event; batch callers that want one aggregated event
+    //across many tokens should use `internal_transfer_maybe_logged` instead.
     pub(crate) fn internal_transfer(
         &mut self,
         sender_id: &AccountId,
@@ -130,26 +164,49 @@ impl Contract {
         approval_id: Option<u64>,
         memo: Option<String>,
     ) -> Token {
+        self.internal_transfer_maybe_logged(sender_id, receiver_id, token_id, approval_id, memo, true)
+    }
+
+    //same as `internal_transfer`, but the caller decides whether this move gets its own
+    //NftTransfer event (`emit_event = false` lets a batch method collect moves and emit one log)
+    pub(crate) fn internal_transfer_maybe_logged(
+        &mut self,
+        sender_id: &AccountId,
+        receiver_id: &AccountId,
+        token_id: &TokenId,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+        emit_event: bool,
+    ) -> Token {
+        self.assert_not_paused();
+
         //get the token object by passing in the token_id
         let token = self.tokens_by_id.get(token_id).expect("No token");
 
 		if sender_id != &token.owner_id {
-			//if the token's approved account IDs doesn't contain the sender, we panic
-			if !token.approved_account_ids.contains_key(sender_id) {
+			let is_per_token_approved = token.approved_account_ids.contains_key(sender_id);
+			let is_operator = self.internal_prune_and_check_operator(&token.owner_id, sender_id);
+
+			//if the sender is neither a per-token approval nor an unexpired collection-wide operator, we panic
+			if !is_per_token_approved && !is_operator {
 				env::panic_str("Unauthorized");
 			}
 
-			if let Some(enforced_approval_id) = approval_id {
-				let actual_approval_id = token
-					.approved_account_ids
-					.get(sender_id)
-					.expect("Sender must be approved");
-
-                assert_eq!(
-					actual_approval_id, &enforced_approval_id,
-					"The actual approval_id {} is different from the given approval_id {}",
-					actual_approval_id, enforced_approval_id,
-				);
+			//an operator is authorized over the whole collection and has no single per-token
+			//approval_id to check against, so only enforce this for a per-token approval
+			if is_per_token_approved {
+				if let Some(enforced_approval_id) = approval_id {
+					let actual_approval_id = token
+						.approved_account_ids
+						.get(sender_id)
+						.expect("Sender must be approved");
+
+					assert_eq!(
+						actual_approval_id, &enforced_approval_id,
+						"The actual approval_id {} is different from the given approval_id {}",
+						actual_approval_id, enforced_approval_id,
+					);
+				}
 			}
 		}
 
@@ -173,27 +230,54 @@ impl Contract {
             env::log_str(&format!("Memo: {}", memo).to_string());
         }
 
-        let mut authorized_id = None;
-        if approval_id.is_some() {
-            authorized_id = Some(sender_id.to_string());
-        }
+        if emit_event {
+            let mut authorized_id = None;
+            if approval_id.is_some() {
+                authorized_id = Some(sender_id.to_string());
+            }
 
-        let nft_transfer_log: EventLog = EventLog {
-            // Standard name ("nep177").
-            standard: NFT_STANDARD_NAME.to_string(),
-            // Version of the standard ("nft-2.0.0").
-            version: NFT_METADATA_SPEC.to_string(),
-            event: EventLogVariant::NftTransfer(vec![NftTransferLog {
-                authorized_id,
-                old_owner_id: token.owner_id.to_string(),
-                new_owner_id: receiver_id.to_string(),
-                token_ids: vec![token_id.to_string()],
-                memo,
-            }]),
-        };
+            let nft_transfer_log: EventLog = EventLog {
+                // Standard name ("nep177").
+                standard: NFT_STANDARD_NAME.to_string(),
+                // Version of the standard ("nft-2.0.0").
+                version: NFT_METADATA_SPEC.to_string(),
+                event: EventLogVariant::NftTransfer(vec![NftTransferLog {
+                    authorized_id,
+                    old_owner_id: token.owner_id.to_string(),
+                    new_owner_id: receiver_id.to_string(),
+                    token_ids: vec![token_id.to_string()],
+                    memo,
+                }]),
+            };
+
+            env::log_str(&nft_transfer_log.to_string());
+        }
 
-        env::log_str(&nft_transfer_log.to_string());
-        
         token
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_expiration_is_always_active() {
+        assert!(operator_is_active(None, 1_000));
+    }
+
+    #[test]
+    fn not_yet_expired_is_active() {
+        assert!(operator_is_active(Some(2_000), 1_000));
+    }
+
+    #[test]
+    fn exactly_at_expiration_is_inactive() {
+        assert!(!operator_is_active(Some(1_000), 1_000));
+    }
+
+    #[test]
+    fn past_expiration_is_inactive() {
+        assert!(!operator_is_active(Some(500), 1_000));
+    }
 } 
\ No newline at end of file
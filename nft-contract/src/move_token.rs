@@ -0,0 +1,122 @@
+use crate::*;
+use near_sdk::{ext_contract, Gas, PromiseResult};
+
+const GAS_FOR_NFT_ON_MOVE: Gas = Gas(30_000_000_000_000);
+const GAS_FOR_ON_MOVE_CALLBACK: Gas = Gas(10_000_000_000_000);
+
+#[ext_contract(ext_nft_on_move)]
+trait NonFungibleTokenMoveReceiver {
+    //cross contract call on the destination contract, asked to re-mint the moved token
+    fn nft_on_move(&mut self, token: Token, token_metadata: TokenMetadata, royalty: Option<Royalty>);
+}
+
+#[ext_contract(ext_self)]
+trait NonFungibleTokenMoveResolver {
+    fn on_move_callback(&mut self, token_id: TokenId, contract_id: AccountId) -> bool;
+}
+
+//only a clean Successful promise counts as the destination contract accepting the token;
+//anything else (Failed, or still NotReady) means the local burn needs to be rolled back.
+fn move_succeeded(result: &PromiseResult) -> bool {
+    matches!(result, PromiseResult::Successful(_))
+}
+
+#[near_bindgen]
+impl Contract {
+    //burn the token on this contract and ask `contract_id` to re-mint it there. Only finalizes
+    //the local burn once the destination contract has accepted the token.
+    #[payable]
+    pub fn nft_move(&mut self, token_id: TokenId, contract_id: AccountId) {
+        assert_one_yocto();
+        assert!(self.allow_moves, "Moving tokens to another contract is disabled");
+
+        let token = self.tokens_by_id.get(&token_id).expect("No token");
+        let predecessor_account_id = env::predecessor_account_id();
+
+        assert_eq!(
+            &predecessor_account_id, &token.owner_id,
+            "Predecessor must be the token owner."
+        );
+        assert!(
+            token.approved_account_ids.is_empty(),
+            "Cannot move a token that has active approvals"
+        );
+
+        let token_metadata = self.token_metadata_by_id.get(&token_id).expect("No metadata for token");
+        let royalty = if token.royalty.is_empty() {
+            None
+        } else {
+            Some(token.royalty.clone())
+        };
+
+        //remove the token locally up front, but keep a copy around in case the callback needs
+        //to restore it after the destination contract rejects it
+        self.internal_remove_token_from_owner(&token.owner_id, &token_id);
+        self.tokens_by_id.remove(&token_id);
+        self.restorable_token_for_move.insert(&token_id, &token);
+
+        ext_nft_on_move::ext(contract_id.clone())
+            .with_static_gas(GAS_FOR_NFT_ON_MOVE)
+            .nft_on_move(token, token_metadata, royalty)
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_ON_MOVE_CALLBACK)
+                    .on_move_callback(token_id, contract_id),
+            );
+    }
+
+    //private callback chained after `nft_on_move`. Finalizes the burn on success, otherwise
+    //restores the token to its owner.
+    #[private]
+    pub fn on_move_callback(&mut self, token_id: TokenId, contract_id: AccountId) -> bool {
+        let succeeded = move_succeeded(&env::promise_result(0));
+        let stashed_token = self.restorable_token_for_move.remove(&token_id);
+
+        if succeeded {
+            self.token_metadata_by_id.remove(&token_id);
+
+            let owner_id = stashed_token.map(|token| token.owner_id.to_string()).unwrap_or_default();
+            let nft_burn_log: EventLog = EventLog {
+                standard: NFT_STANDARD_NAME.to_string(),
+                version: NFT_METADATA_SPEC.to_string(),
+                event: EventLogVariant::NftBurn(vec![NftBurnLog {
+                    owner_id,
+                    authorized_id: None,
+                    token_ids: vec![token_id.to_string()],
+                    memo: Some(format!("moved to {}", contract_id)),
+                }]),
+            };
+            env::log_str(&nft_burn_log.to_string());
+
+            true
+        } else {
+            //the destination contract rejected (or failed to accept) the token; restore it
+            if let Some(token) = stashed_token {
+                self.internal_add_token_to_owner(&token.owner_id, &token_id);
+                self.tokens_by_id.insert(&token_id, &token);
+            }
+
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn successful_promise_means_the_move_succeeded() {
+        assert!(move_succeeded(&PromiseResult::Successful(vec![])));
+    }
+
+    #[test]
+    fn failed_promise_means_the_move_did_not_succeed() {
+        assert!(!move_succeeded(&PromiseResult::Failed));
+    }
+
+    #[test]
+    fn not_ready_promise_means_the_move_did_not_succeed() {
+        assert!(!move_succeeded(&PromiseResult::NotReady));
+    }
+}
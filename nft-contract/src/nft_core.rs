@@ -0,0 +1,282 @@
+use crate::*;
+use near_sdk::{ext_contract, Gas, PromiseOrValue, PromiseResult};
+
+const GAS_FOR_RESOLVE_TRANSFER: Gas = Gas(5_000_000_000_000);
+const GAS_FOR_NFT_ON_TRANSFER: Gas = Gas(25_000_000_000_000);
+//minimum gas a caller must attach so both the receiver callback and the resolver have a budget
+const GAS_FOR_NFT_TRANSFER_CALL: Gas = Gas(GAS_FOR_RESOLVE_TRANSFER.0 + GAS_FOR_NFT_ON_TRANSFER.0);
+
+pub trait NonFungibleTokenCore {
+    //transfer the token to the receiver, then let the receiver decide whether to keep it
+    fn nft_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+        msg: String,
+    ) -> PromiseOrValue<bool>;
+}
+
+#[ext_contract(ext_nft_receiver)]
+trait NonFungibleTokenReceiver {
+    //cross contract call the receiver contract is expected to implement
+    fn nft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        previous_owner_id: AccountId,
+        token_id: TokenId,
+        msg: String,
+    ) -> Promise;
+}
+
+#[ext_contract(ext_self)]
+trait NonFungibleTokenResolver {
+    fn nft_resolve_transfer(
+        &mut self,
+        owner_id: AccountId,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        approved_account_ids: HashMap<AccountId, u64>,
+    ) -> bool;
+}
+
+//interpret the receiver's `nft_on_transfer` return value: any value other than a clean `false`
+//(including malformed JSON) is treated as "keep reverting", matching the conservative default
+//the near-contract-standards resolver uses.
+fn parse_transfer_outcome(value: &[u8]) -> bool {
+    near_sdk::serde_json::from_slice::<bool>(value).unwrap_or(true)
+}
+
+#[near_bindgen]
+impl NonFungibleTokenCore for Contract {
+    //transfer the token and notify the receiver, reverting ownership if it rejects the token
+    #[payable]
+    fn nft_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+        msg: String,
+    ) -> PromiseOrValue<bool> {
+        assert_one_yocto();
+        assert!(
+            env::prepaid_gas() > GAS_FOR_NFT_TRANSFER_CALL,
+            "You cannot attach less than {:?} Gas to nft_transfer_call",
+            GAS_FOR_NFT_TRANSFER_CALL,
+        );
+
+        let sender_id = env::predecessor_account_id();
+        let previous_token = self.internal_transfer(
+            &sender_id,
+            &receiver_id,
+            &token_id,
+            approval_id,
+            memo,
+        );
+
+        ext_nft_receiver::ext(receiver_id.clone())
+            .with_static_gas(GAS_FOR_NFT_ON_TRANSFER)
+            .nft_on_transfer(
+                sender_id,
+                previous_token.owner_id.clone(),
+                token_id.clone(),
+                msg,
+            )
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_RESOLVE_TRANSFER)
+                    .nft_resolve_transfer(
+                        previous_token.owner_id,
+                        receiver_id,
+                        token_id,
+                        previous_token.approved_account_ids,
+                    ),
+            )
+            .into()
+    }
+}
+
+//group per-token transfer results by their *actual* previous owner and authorized_id into one
+//`NftTransferLog` per group, rather than assuming the predecessor owned every token.
+fn group_batch_transfer_logs(
+    entries: Vec<(AccountId, Option<AccountId>, TokenId)>,
+    new_owner_id: &AccountId,
+) -> Vec<NftTransferLog> {
+    let mut groups: HashMap<(AccountId, Option<AccountId>), Vec<String>> = HashMap::new();
+
+    for (old_owner_id, authorized_id, token_id) in entries {
+        groups
+            .entry((old_owner_id, authorized_id))
+            .or_default()
+            .push(token_id);
+    }
+
+    groups
+        .into_iter()
+        .map(|((old_owner_id, authorized_id), token_ids)| NftTransferLog {
+            authorized_id: authorized_id.map(|id| id.to_string()),
+            old_owner_id: old_owner_id.to_string(),
+            new_owner_id: new_owner_id.to_string(),
+            token_ids,
+            memo: None,
+        })
+        .collect()
+}
+
+#[near_bindgen]
+impl Contract {
+    //transfer many tokens in one call, emitting a single aggregated NftTransfer event instead
+    //of one log per token. Tokens are grouped by their *actual* previous owner and authorized_id
+    //(as validated per-token by internal_transfer_maybe_logged) rather than assuming the
+    //predecessor owned every token, so an approved account or operator batch-moving tokens on
+    //behalf of several owners still produces a correctly attributed log.
+    #[payable]
+    pub fn nft_batch_transfer(
+        &mut self,
+        receiver_id: AccountId,
+        token_ids: Vec<(TokenId, Option<u64>, Option<String>)>,
+    ) {
+        assert_one_yocto();
+
+        let sender_id = env::predecessor_account_id();
+        let mut entries: Vec<(AccountId, Option<AccountId>, TokenId)> = Vec::new();
+
+        for (token_id, approval_id, memo) in token_ids {
+            let previous_token = self.internal_transfer_maybe_logged(
+                &sender_id,
+                &receiver_id,
+                &token_id,
+                approval_id,
+                memo,
+                false,
+            );
+
+            let authorized_id = approval_id.map(|_| sender_id.clone());
+            entries.push((previous_token.owner_id, authorized_id, token_id));
+        }
+
+        let nft_transfer_log: EventLog = EventLog {
+            standard: NFT_STANDARD_NAME.to_string(),
+            version: NFT_METADATA_SPEC.to_string(),
+            event: EventLogVariant::NftTransfer(group_batch_transfer_logs(entries, &receiver_id)),
+        };
+
+        env::log_str(&nft_transfer_log.to_string());
+    }
+
+    //private callback chained after `nft_on_transfer`. Reverts the transfer back to
+    //`owner_id` unless the receiver signalled it wants to keep the token.
+    #[private]
+    pub fn nft_resolve_transfer(
+        &mut self,
+        owner_id: AccountId,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        approved_account_ids: HashMap<AccountId, u64>,
+    ) -> bool {
+        let must_revert = match env::promise_result(0) {
+            PromiseResult::NotReady => env::abort(),
+            PromiseResult::Successful(value) => parse_transfer_outcome(&value),
+            PromiseResult::Failed => true,
+        };
+
+        if !must_revert {
+            return true;
+        }
+
+        //the receiver rejected the token (or the call failed); move it back to the previous owner
+        let token = match self.tokens_by_id.get(&token_id) {
+            Some(token) => token,
+            None => return true,
+        };
+
+        if token.owner_id != receiver_id {
+            return true;
+        }
+
+        self.internal_remove_token_from_owner(&receiver_id, &token_id);
+        self.internal_add_token_to_owner(&owner_id, &token_id);
+
+        let reverted_token = Token {
+            owner_id: owner_id.clone(),
+            approved_account_ids,
+            next_approval_id: token.next_approval_id,
+            royalty: token.royalty,
+        };
+        self.tokens_by_id.insert(&token_id, &reverted_token);
+
+        let nft_transfer_log: EventLog = EventLog {
+            standard: NFT_STANDARD_NAME.to_string(),
+            version: NFT_METADATA_SPEC.to_string(),
+            event: EventLogVariant::NftTransfer(vec![NftTransferLog {
+                authorized_id: None,
+                old_owner_id: receiver_id.to_string(),
+                new_owner_id: owner_id.to_string(),
+                token_ids: vec![token_id.to_string()],
+                memo: Some("revert nft_transfer_call".to_string()),
+            }]),
+        };
+        env::log_str(&nft_transfer_log.to_string());
+
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explicit_false_keeps_the_transfer() {
+        assert!(!parse_transfer_outcome(b"false"));
+    }
+
+    #[test]
+    fn explicit_true_reverts_the_transfer() {
+        assert!(parse_transfer_outcome(b"true"));
+    }
+
+    #[test]
+    fn malformed_json_defaults_to_reverting() {
+        assert!(parse_transfer_outcome(b"not json"));
+    }
+
+    fn account(name: &str) -> AccountId {
+        name.parse().unwrap()
+    }
+
+    #[test]
+    fn tokens_from_the_same_owner_and_approval_share_one_log() {
+        let receiver = account("receiver.near");
+        let logs = group_batch_transfer_logs(
+            vec![
+                (account("owner.near"), None, "1".to_string()),
+                (account("owner.near"), None, "2".to_string()),
+            ],
+            &receiver,
+        );
+
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].old_owner_id, "owner.near");
+        assert_eq!(logs[0].token_ids, vec!["1".to_string(), "2".to_string()]);
+    }
+
+    #[test]
+    fn tokens_from_different_owners_get_separate_logs() {
+        let receiver = account("receiver.near");
+        let logs = group_batch_transfer_logs(
+            vec![
+                (account("alice.near"), None, "1".to_string()),
+                (account("bob.near"), Some(account("operator.near")), "2".to_string()),
+            ],
+            &receiver,
+        );
+
+        assert_eq!(logs.len(), 2);
+        let bob_log = logs.iter().find(|log| log.old_owner_id == "bob.near").unwrap();
+        assert_eq!(bob_log.authorized_id.as_deref(), Some("operator.near"));
+        assert_eq!(bob_log.new_owner_id, "receiver.near");
+    }
+}
@@ -16,40 +16,45 @@ pub trait NonFungibleTokenCore {
     ) -> Payout;
 }
 
-#[near_bindgen]
-impl NonFungibleTokenCore for Contract {
-
-    //calculates the payout for a token given the passed in balance. This is a view method
-    fn nft_payout(&self, token_id: TokenId, balance: U128, max_len_payout: u32) -> Payout {
+impl Contract {
+    //builds a payout map for `balance` given a token's royalty split: every non-owner royalty
+    //recipient gets their percentage-based cut, and the owner gets whatever is left over so the
+    //sum of all payouts always equals `balance` exactly, rounding dust included.
+    fn internal_build_payout(owner_id: &AccountId, royalty: &Royalty, balance: Balance, max_len_payout: u32) -> Payout {
+        assert!(royalty.len() as u32 <= max_len_payout, "Market cannot payout to that many receivers");
 
-		let token = self.tokens_by_id.get(&token_id).expect("No token");
-
-        let owner_id = token.owner_id;
-        let mut total_perpetual = 0;
-        let balance_u128 = u128::from(balance);
         let mut payout_object = Payout {
-            payout: HashMap::new()
+            payout: HashMap::new(),
         };
-		let royalty = token.royalty;
 
-		assert!(royalty.len() as u32 <= max_len_payout, "Market cannot payout to that many receivers");
+        let mut total_paid_out: Balance = 0;
 
-        //go through each key and value in the royalty object
-		for (k, v) in royalty.iter() {
-            //get the key
-			let key = k.clone();
-            //only insert into the payout if the key isn't the token owner (we add their payout at the end)
-			if key != owner_id {
-                //
-				payout_object.payout.insert(key, royalty_to_payout(*v, balance_u128));
-				total_perpetual += *v;
-			}
-		}
+        for (account_id, percentage) in royalty.iter() {
+            if account_id != owner_id {
+                let amount = royalty_to_payout(*percentage, balance);
+                total_paid_out += amount.0;
+                payout_object.payout.insert(account_id.clone(), amount);
+            }
+        }
 
-		payout_object.payout.insert(owner_id, royalty_to_payout(10000 - total_perpetual, balance_u128));
+        //the owner's share is whatever remains, so rounding dust never leaves the payout short
+        payout_object
+            .payout
+            .insert(owner_id.clone(), U128(balance - total_paid_out));
 
-		payout_object
-	}
+        payout_object
+    }
+}
+
+#[near_bindgen]
+impl NonFungibleTokenCore for Contract {
+
+    //calculates the payout for a token given the passed in balance. This is a view method
+    fn nft_payout(&self, token_id: TokenId, balance: U128, max_len_payout: u32) -> Payout {
+        let token = self.tokens_by_id.get(&token_id).expect("No token");
+
+        Contract::internal_build_payout(&token.owner_id, &token.royalty, balance.0, max_len_payout)
+    }
 
     //transfers the token to the receiver ID and returns the payout object that should be payed given the passed in balance. 
     #[payable]
@@ -72,36 +77,134 @@ impl NonFungibleTokenCore for Contract {
             memo,
         );
 
-        refund_approved_account_ids(
-            previous_token.owner_id.clone(),
-            &previous_token.approved_account_ids,
-        );
+        //the cleared approvals' storage was prepaid through the storage-balance ledger (see
+        //approval.rs), so it's credited back there rather than refunded as a direct transfer
+        let storage_released: u64 = previous_token
+            .approved_account_ids
+            .keys()
+            .map(bytes_for_approved_account_id)
+            .sum();
+        self.internal_storage_credit(&previous_token.owner_id, storage_released);
 
+        Contract::internal_build_payout(&previous_token.owner_id, &previous_token.royalty, balance.0, max_len_payout)
+    }
+}
 
-        let owner_id = previous_token.owner_id;
-        let mut total_perpetual = 0;
-        let balance_u128 = u128::from(balance);
-        let mut payout_object = Payout {
-            payout: HashMap::new()
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::VMContextBuilder;
+    use near_sdk::testing_env;
+
+    fn account(name: &str) -> AccountId {
+        name.parse().unwrap()
+    }
+
+    fn context(predecessor: AccountId, attached_deposit: Balance) {
+        let mut builder = VMContextBuilder::new();
+        builder.predecessor_account_id(predecessor);
+        builder.attached_deposit(attached_deposit);
+        testing_env!(builder.build());
+    }
+
+    //end-to-end regression test for the bug where a sender authorized purely through a
+    //collection-wide `nft_approve_all` operator grant (no per-token `approved_account_ids`
+    //entry) could never actually settle `nft_transfer_payout`, because the approval_id check
+    //looked the sender up in the per-token map unconditionally and panicked "Sender must be
+    //approved". Exercises the real #[near_bindgen] method end to end rather than a pure helper.
+    #[test]
+    fn operator_approval_can_settle_a_transfer_payout() {
+        let owner = account("owner.near");
+        let operator = account("operator.near");
+        let receiver = account("receiver.near");
+        let token_id: TokenId = "token-1".to_string();
+
+        context(owner.clone(), 0);
+        let mut contract = Contract::new_default_meta(owner.clone());
+
+        let token = Token {
+            owner_id: owner.clone(),
+            approved_account_ids: HashMap::new(),
+            next_approval_id: 0,
+            royalty: Royalty::new(),
+        };
+        contract.tokens_by_id.insert(&token_id, &token);
+        contract.internal_add_token_to_owner(&owner, &token_id);
+
+        let mut operators: HashMap<AccountId, Option<u64>> = HashMap::new();
+        operators.insert(operator.clone(), None);
+        contract.operator_approvals.insert(&owner, &operators);
+
+        //the marketplace has no per-token approval_id to pass for an operator-wide grant; any
+        //value must be accepted since the per-token approval_id check doesn't apply here
+        context(operator.clone(), 1);
+        let payout = contract.nft_transfer_payout(receiver.clone(), token_id.clone(), 0, None, U128(100), 10);
+
+        assert_eq!(payout.payout.get(&owner).unwrap().0, 100);
+        assert_eq!(contract.tokens_by_id.get(&token_id).unwrap().owner_id, receiver);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unauthorized")]
+    fn non_operator_non_approved_sender_still_cannot_transfer_payout() {
+        let owner = account("owner.near");
+        let stranger = account("stranger.near");
+        let receiver = account("receiver.near");
+        let token_id: TokenId = "token-1".to_string();
+
+        context(owner.clone(), 0);
+        let mut contract = Contract::new_default_meta(owner.clone());
+
+        let token = Token {
+            owner_id: owner.clone(),
+            approved_account_ids: HashMap::new(),
+            next_approval_id: 0,
+            royalty: Royalty::new(),
         };
-		let royalty = previous_token.royalty;
+        contract.tokens_by_id.insert(&token_id, &token);
+        contract.internal_add_token_to_owner(&owner, &token_id);
 
-		assert!(royalty.len() as u32 <= max_len_payout, "Market cannot payout to that many receivers");
+        context(stranger, 1);
+        contract.nft_transfer_payout(receiver, token_id, 0, None, U128(100), 10);
+    }
 
-        //go through each key and value in the royalty object
-		for (k, v) in royalty.iter() {
-            //get the key
-			let key = k.clone();
-            //only insert into the payout if the key isn't the token owner (we add their payout at the end)
-			if key != owner_id {
-                //
-				payout_object.payout.insert(key, royalty_to_payout(*v, balance_u128));
-				total_perpetual += *v;
-			}
-		}
+    #[test]
+    fn payout_sums_to_exact_balance_with_dust() {
+        let owner = account("owner.near");
+        let mut royalty = Royalty::new();
+        royalty.insert(account("creator_a.near"), 3333);
+        royalty.insert(account("creator_b.near"), 3333);
+
+        let payout = Contract::internal_build_payout(&owner, &royalty, 100, 10);
+
+        let total: Balance = payout.payout.values().map(|amount| amount.0).sum();
+        assert_eq!(total, 100);
+        //33.33% (3333 basis points) of 100 truncates to 33 for each non-owner recipient, leaving 34 (the rounding
+        //dust) for the owner rather than losing it
+        assert_eq!(payout.payout.get(&account("creator_a.near")).unwrap().0, 33);
+        assert_eq!(payout.payout.get(&account("creator_b.near")).unwrap().0, 33);
+        assert_eq!(payout.payout.get(&owner).unwrap().0, 34);
+    }
+
+    #[test]
+    fn payout_with_no_royalty_sends_everything_to_owner() {
+        let owner = account("owner.near");
+        let royalty = Royalty::new();
+
+        let payout = Contract::internal_build_payout(&owner, &royalty, 500, 10);
+
+        assert_eq!(payout.payout.len(), 1);
+        assert_eq!(payout.payout.get(&owner).unwrap().0, 500);
+    }
 
-		payout_object.payout.insert(owner_id, royalty_to_payout(10000 - total_perpetual, balance_u128));
+    #[test]
+    #[should_panic(expected = "Market cannot payout to that many receivers")]
+    fn payout_rejects_too_many_receivers() {
+        let owner = account("owner.near");
+        let mut royalty = Royalty::new();
+        royalty.insert(account("creator_a.near"), 5000);
+        royalty.insert(account("creator_b.near"), 5000);
 
-		payout_object
+        Contract::internal_build_payout(&owner, &royalty, 100, 1);
     }
 }
\ No newline at end of file
@@ -0,0 +1,277 @@
+use crate::*;
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StorageBalance {
+    pub total: U128,
+    pub available: U128,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StorageBalanceBounds {
+    pub min: U128,
+    pub max: Option<U128>,
+}
+
+//the ledger math lives here, separate from `env::storage_byte_cost()`, so total/available are
+//updated the same way no matter which `internal_storage_*` caller touches them: a deposit
+//raises both, while a debit or credit only moves `available`.
+fn storage_balance_after_deposit(balance: &StorageBalance, amount: Balance) -> StorageBalance {
+    StorageBalance {
+        total: U128(balance.total.0 + amount),
+        available: U128(balance.available.0 + amount),
+    }
+}
+
+fn storage_balance_after_debit(balance: &StorageBalance, cost: Balance) -> StorageBalance {
+    assert!(
+        balance.available.0 >= cost,
+        "Insufficient storage balance; call storage_deposit to cover {} yoctoNEAR",
+        cost
+    );
+
+    StorageBalance {
+        total: balance.total,
+        available: U128(balance.available.0 - cost),
+    }
+}
+
+fn storage_balance_after_credit(balance: &StorageBalance, cost: Balance) -> StorageBalance {
+    StorageBalance {
+        total: balance.total,
+        available: U128(balance.available.0 + cost),
+    }
+}
+
+//split a `registration_only` deposit into (amount actually credited to the ledger, amount
+//refunded to the caller). An already-registered account gets the whole deposit back; a fresh
+//account only gets `min_balance` credited, with the remainder refunded.
+fn registration_only_split(amount: Balance, min_balance: Balance, already_registered: bool) -> (Balance, Balance) {
+    assert!(
+        already_registered || amount >= min_balance,
+        "Requires at least {} yoctoNEAR to register",
+        min_balance
+    );
+
+    let to_deposit = if already_registered { 0 } else { min_balance };
+    (to_deposit, amount - to_deposit)
+}
+
+#[near_bindgen]
+impl Contract {
+    //prepay storage so approvals can be debited from the depositor's balance instead of
+    //attaching their full storage cost on every nft_approve/nft_approve_all call (those calls
+    //still require at least 1 yoctoNEAR attached).
+    //`registration_only` caps what's actually credited at `storage_balance_bounds().min` and
+    //refunds the remainder, for callers that only want to register rather than fully fund a
+    //balance up front.
+    #[payable]
+    pub fn storage_deposit(
+        &mut self,
+        account_id: Option<AccountId>,
+        registration_only: Option<bool>,
+    ) -> StorageBalance {
+        let amount: Balance = env::attached_deposit();
+        assert!(amount > 0, "Requires attached deposit to be greater than 0");
+
+        let account_id = account_id.unwrap_or_else(env::predecessor_account_id);
+
+        if !registration_only.unwrap_or(false) {
+            return self.internal_storage_deposit(&account_id, amount);
+        }
+
+        let min_balance = self.storage_balance_bounds().min.0;
+        let already_registered = self.storage_deposits.get(&account_id).is_some();
+        let (to_deposit, refund) = registration_only_split(amount, min_balance, already_registered);
+
+        let balance = if to_deposit > 0 {
+            self.internal_storage_deposit(&account_id, to_deposit)
+        } else {
+            self.storage_deposits.get(&account_id).expect("Account is not registered")
+        };
+
+        if refund > 0 {
+            Promise::new(account_id).transfer(refund);
+        }
+
+        balance
+    }
+
+    //withdraw any `available` balance that isn't currently locked by outstanding approvals
+    #[payable]
+    pub fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance {
+        assert_one_yocto();
+
+        let account_id = env::predecessor_account_id();
+        let mut balance = self
+            .storage_deposits
+            .get(&account_id)
+            .expect("Account is not registered");
+
+        let to_withdraw = amount.map(|a| a.0).unwrap_or(balance.available.0);
+        assert!(
+            to_withdraw <= balance.available.0,
+            "Cannot withdraw more than the available storage balance"
+        );
+
+        balance.total = U128(balance.total.0 - to_withdraw);
+        balance.available = U128(balance.available.0 - to_withdraw);
+        self.storage_deposits.insert(&account_id, &balance);
+
+        if to_withdraw > 0 {
+            Promise::new(account_id).transfer(to_withdraw);
+        }
+
+        balance
+    }
+
+    pub fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance> {
+        self.storage_deposits.get(&account_id)
+    }
+
+    pub fn storage_balance_bounds(&self) -> StorageBalanceBounds {
+        StorageBalanceBounds {
+            min: U128(Balance::from(bytes_for_approved_account_id(&env::current_account_id())) * env::storage_byte_cost()),
+            max: None,
+        }
+    }
+
+    //credit `amount` yoctoNEAR into `account_id`'s prepaid storage balance and return the updated balance
+    pub(crate) fn internal_storage_deposit(&mut self, account_id: &AccountId, amount: Balance) -> StorageBalance {
+        let balance = self.storage_deposits.get(account_id).unwrap_or(StorageBalance {
+            total: U128(0),
+            available: U128(0),
+        });
+        let balance = storage_balance_after_deposit(&balance, amount);
+
+        self.storage_deposits.insert(account_id, &balance);
+
+        balance
+    }
+
+    //debit `bytes` worth of storage cost from `account_id`'s prepaid balance, panicking if they
+    //haven't deposited enough to cover it
+    pub(crate) fn internal_storage_debit(&mut self, account_id: &AccountId, bytes: u64) {
+        if bytes == 0 {
+            return;
+        }
+
+        let cost = Balance::from(bytes) * env::storage_byte_cost();
+        let balance = self
+            .storage_deposits
+            .get(account_id)
+            .expect("Must call storage_deposit before approving accounts");
+
+        let balance = storage_balance_after_debit(&balance, cost);
+        self.storage_deposits.insert(account_id, &balance);
+    }
+
+    //credit `bytes` worth of storage cost back to `account_id`'s prepaid balance
+    pub(crate) fn internal_storage_credit(&mut self, account_id: &AccountId, bytes: u64) {
+        if bytes == 0 {
+            return;
+        }
+
+        let cost = Balance::from(bytes) * env::storage_byte_cost();
+        if let Some(balance) = self.storage_deposits.get(account_id) {
+            let balance = storage_balance_after_credit(&balance, cost);
+            self.storage_deposits.insert(account_id, &balance);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::VMContextBuilder;
+    use near_sdk::testing_env;
+
+    fn balance(total: u128, available: u128) -> StorageBalance {
+        StorageBalance {
+            total: U128(total),
+            available: U128(available),
+        }
+    }
+
+    fn account(name: &str) -> AccountId {
+        name.parse().unwrap()
+    }
+
+    fn context(predecessor: AccountId, attached_deposit: Balance) {
+        let mut builder = VMContextBuilder::new();
+        builder.predecessor_account_id(predecessor);
+        builder.attached_deposit(attached_deposit);
+        testing_env!(builder.build());
+    }
+
+    #[test]
+    fn registration_only_on_a_new_account_credits_only_the_minimum() {
+        let (to_deposit, refund) = registration_only_split(1_000, 300, false);
+        assert_eq!(to_deposit, 300);
+        assert_eq!(refund, 700);
+    }
+
+    #[test]
+    fn registration_only_on_an_already_registered_account_refunds_everything() {
+        let (to_deposit, refund) = registration_only_split(1_000, 300, true);
+        assert_eq!(to_deposit, 0);
+        assert_eq!(refund, 1_000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Requires at least")]
+    fn registration_only_on_a_new_account_rejects_an_underfunded_deposit() {
+        registration_only_split(100, 300, false);
+    }
+
+    //end-to-end regression test for the missing `registration_only` parameter: a caller that
+    //only wants to register should have their deposit capped at the minimum balance, with the
+    //rest refunded, rather than the whole attached deposit being credited.
+    #[test]
+    fn storage_deposit_registration_only_caps_the_ledger_at_the_minimum_balance() {
+        let alice = account("alice.near");
+        context(alice.clone(), 10_000_000_000_000_000_000_000);
+
+        let mut contract = Contract::new_default_meta(account("owner.near"));
+        let balance = contract.storage_deposit(Some(alice.clone()), Some(true));
+
+        let min_balance = contract.storage_balance_bounds().min.0;
+        assert_eq!(balance.total.0, min_balance);
+        assert_eq!(balance.available.0, min_balance);
+    }
+
+    #[test]
+    fn deposit_increases_total_and_available_by_the_same_amount() {
+        let after = storage_balance_after_deposit(&balance(100, 40), 50);
+        assert_eq!(after.total.0, 150);
+        assert_eq!(after.available.0, 90);
+    }
+
+    #[test]
+    fn debit_only_reduces_available() {
+        let after = storage_balance_after_debit(&balance(100, 80), 30);
+        assert_eq!(after.total.0, 100);
+        assert_eq!(after.available.0, 50);
+    }
+
+    #[test]
+    #[should_panic(expected = "Insufficient storage balance")]
+    fn debit_panics_when_available_is_too_low() {
+        storage_balance_after_debit(&balance(100, 20), 30);
+    }
+
+    #[test]
+    fn credit_only_increases_available() {
+        let after = storage_balance_after_credit(&balance(100, 20), 30);
+        assert_eq!(after.total.0, 100);
+        assert_eq!(after.available.0, 50);
+    }
+
+    #[test]
+    fn credit_then_debit_round_trips_to_the_same_balance() {
+        let original = balance(100, 60);
+        let after = storage_balance_after_debit(&storage_balance_after_credit(&original, 40), 40);
+        assert_eq!(after.available.0, original.available.0);
+    }
+}